@@ -45,6 +45,32 @@ impl<T:Clone> Matrix<T> {
 		// Return the element at row, col.
 		self.data[row][col].clone()
 	}
+	pub fn from_vec(m : uint, n : uint, data : ~[T]) -> Matrix<T> {
+		// Build an MxN matrix by reshaping a flat row-major vector.
+		assert!(data.len() == m*n);
+		Matrix::from_fn(m, n, |i,j| { data[i*n+j].clone() })
+	}
+	pub fn try_from_iter<I:Iterator<T>>(m : uint, n : uint, iter : I) -> Option<Matrix<T>> {
+		// Pull exactly m*n elements from iter, or None if the count doesn't match.
+		let mut iter = iter;
+		let mut data = vec::with_capacity(m*n);
+		for _ in range(0, m*n) {
+			match iter.next() {
+				Some(v) => data.push(v),
+				None => return None
+			}
+		}
+		if iter.next().is_some() {
+			return None;
+		}
+		Some(Matrix::from_vec(m, n, data))
+	}
+	pub fn to_vec(&self) -> ~[T] {
+		// Flatten the matrix into a row-major vector.
+		let mut out = vec::with_capacity(self.m*self.n);
+		self.apply(|i,j| { out.push(self.at(i,j)); });
+		out
+	}
 	pub fn row(&self, row : uint) -> Matrix<T> {
 		// Return row r from an MxN matrix as a 1xN matrix.
 		Matrix{m: 1, n:self.n, data: ~[self.data[row].to_owned()]}
@@ -77,6 +103,81 @@ impl<T:Clone> Matrix<T> {
 	pub fn map(&self, mapper : |T| -> T) -> Matrix<T> {
 		Matrix::from_fn(self.m, self.n, |i,j| { mapper(self.at(i,j)) })
 	}
+	pub fn remove_row(&self, r : uint) -> Matrix<T> {
+		// Return a copy of the matrix with row r removed.
+		assert!(r < self.m);
+		Matrix::from_fn(self.m-1, self.n, |i,j| {
+			let si = if i < r { i } else { i+1 };
+			self.at(si, j)
+		})
+	}
+	pub fn remove_col(&self, c : uint) -> Matrix<T> {
+		// Return a copy of the matrix with column c removed.
+		assert!(c < self.n);
+		Matrix::from_fn(self.m, self.n-1, |i,j| {
+			let sj = if j < c { j } else { j+1 };
+			self.at(i, sj)
+		})
+	}
+	pub fn insert_row(&self, r : uint, data : &[T]) -> Matrix<T> {
+		// Return a copy of the matrix with data inserted as a new row at position r.
+		assert!(r <= self.m && data.len() == self.n);
+		Matrix::from_fn(self.m+1, self.n, |i,j| {
+			if i < r { self.at(i, j) }
+			else if i == r { data[j].clone() }
+			else { self.at(i-1, j) }
+		})
+	}
+	pub fn insert_col(&self, c : uint, data : &[T]) -> Matrix<T> {
+		// Return a copy of the matrix with data inserted as a new column at position c.
+		assert!(c <= self.n && data.len() == self.m);
+		Matrix::from_fn(self.m, self.n+1, |i,j| {
+			if j < c { self.at(i, j) }
+			else if j == c { data[i].clone() }
+			else { self.at(i, j-1) }
+		})
+	}
+	pub fn append_row(&self, data : &[T]) -> Matrix<T> {
+		// Append data as a new row at the bottom of the matrix.
+		self.insert_row(self.m, data)
+	}
+	pub fn append_col(&self, data : &[T]) -> Matrix<T> {
+		// Append data as a new column at the right of the matrix.
+		self.insert_col(self.n, data)
+	}
+	pub fn fmap<S:Clone>(&self, f : |T| -> S) -> Matrix<S> {
+		// Like map, but allows the result type to differ from T.
+		Matrix::from_fn(self.m, self.n, |i,j| { f(self.at(i,j)) })
+	}
+	pub fn reduce<S>(&self, init : S, f : |S, T| -> S) -> S {
+		// Fold over all elements in row-major order.
+		let mut acc = init;
+		self.apply(|i,j| { acc = f(acc, self.at(i,j)) });
+		acc
+	}
+	pub fn index_iter(&self) -> ~[(uint, uint)] {
+		// Return all (row, col) index pairs in row-major order.
+		let mut idx = vec::with_capacity(self.m*self.n);
+		self.apply(|i,j| { idx.push((i,j)); });
+		idx
+	}
+	pub fn set(&mut self, row : uint, col : uint, val : T) {
+		// Set the element at row, col in place.
+		self.data[row][col] = val;
+	}
+	pub fn at_mut<'a>(&'a mut self, row : uint, col : uint) -> &'a mut T {
+		// Return a mutable reference to the element at row, col.
+		&mut self.data[row][col]
+	}
+	pub fn map_mut(&mut self, f : |T| -> T) {
+		// Apply f to every element in place.
+		for i in range(0, self.m) {
+			for j in range(0, self.n) {
+				let v = f(self.at(i,j));
+				self.data[i][j] = v;
+			}
+		}
+	}
 }
 
 // methods for Matrix of numbers
@@ -94,6 +195,74 @@ impl<T:Num+Clone> Matrix<T> {
 		}
 		sum
 	}
+	pub fn minor(&self, row : uint, col : uint) -> Matrix<T> {
+		// Return the (M-1)x(N-1) submatrix with the given row and column deleted.
+		assert!(self.m > 1 && self.n > 1);
+		Matrix::from_fn(self.m-1, self.n-1, |i,j| {
+			let si = if i < row { i } else { i+1 };
+			let sj = if j < col { j } else { j+1 };
+			self.at(si, sj)
+		})
+	}
+	pub fn cofactor(&self, i : uint, j : uint) -> T {
+		// (-1)^(i+j) times the determinant of the (i,j) minor.
+		let minor_det = self.minor(i, j).det();
+		if (i+j) % 2 == 0 { minor_det } else { Zero::zero() - minor_det }
+	}
+	pub fn det(&self) -> T {
+		// Determinant via Laplace expansion along the first row.
+		assert!(self.m == self.n);
+		if self.m == 1 {
+			self.at(0, 0)
+		}
+		else if self.m == 2 {
+			self.at(0,0)*self.at(1,1) - self.at(0,1)*self.at(1,0)
+		}
+		else {
+			let mut sum : T = Zero::zero();
+			for j in range(0, self.n) {
+				sum = sum + self.at(0, j) * self.cofactor(0, j);
+			}
+			sum
+		}
+	}
+	pub fn adjugate(&self) -> Matrix<T> {
+		// Transpose of the cofactor matrix.
+		Matrix::from_fn(self.m, self.n, |i,j| { self.cofactor(i, j) }).transpose()
+	}
+	pub fn elem_mul(&self, rhs : &Matrix<T>) -> Matrix<T> {
+		// Multiply matrices elementwise (Hadamard product).
+		assert!(self.size() == rhs.size());
+		Matrix::from_fn(self.m, self.n, |i,j| { self.at(i,j) * rhs.at(i,j) })
+	}
+	pub fn elem_div(&self, rhs : &Matrix<T>) -> Matrix<T> {
+		// Divide matrices elementwise.
+		assert!(self.size() == rhs.size());
+		Matrix::from_fn(self.m, self.n, |i,j| { self.at(i,j) / rhs.at(i,j) })
+	}
+	pub fn scale(&self, factor : T) -> Matrix<T> {
+		// Multiply every element by a scalar.
+		Matrix::from_fn(self.m, self.n, |i,j| { self.at(i,j) * factor })
+	}
+	pub fn add_assign(&mut self, rhs : &Matrix<T>) {
+		// Add rhs into self in place.
+		assert!(self.size() == rhs.size());
+		for i in range(0, self.m) {
+			for j in range(0, self.n) {
+				let v = self.at(i,j) + rhs.at(i,j);
+				self.data[i][j] = v;
+			}
+		}
+	}
+	pub fn scale_mut(&mut self, factor : T) {
+		// Multiply every element by a scalar in place.
+		for i in range(0, self.m) {
+			for j in range(0, self.n) {
+				let v = self.at(i,j) * factor;
+				self.data[i][j] = v;
+			}
+		}
+	}
 }
 
 impl<T:Eq+Clone> Eq for Matrix<T> {
@@ -145,6 +314,13 @@ impl<T:Num+Clone> Mul<Matrix<T>, Matrix<T>> for Matrix<T> {
 	}
 }
 
+// use * with a scalar to scale a matrix
+impl<T:Num+Clone> Mul<T,Matrix<T>> for Matrix<T> {
+	fn mul(&self, rhs: &T) -> Matrix<T> {
+		self.scale(rhs.clone())
+	}
+}
+
 // use [(x,y)] to index matrices
 impl<T:Clone> Index<(uint, uint), T> for Matrix<T> {
 	fn index(&self, &rhs: &(uint, uint)) -> T {
@@ -168,6 +344,109 @@ impl<T:Clone> BitOr<Matrix<T>,Matrix<T>> for Matrix<T> {
 	}
 }
 
+// LU decomposition
+// ----------------
+// Doolittle LU factorization with partial pivoting, for solving linear
+// systems and inverting matrices of f64.
+pub struct LUDecomposition {
+	// combined L (below diagonal) and U (on/above diagonal) matrix
+	data : Matrix<f64>,
+	// row permutation applied while pivoting
+	perm : ~[uint],
+	// sign of the permutation: 1.0 or -1.0
+	parity : f64
+}
+
+impl Matrix<f64> {
+	pub fn lu(&self) -> Option<LUDecomposition> {
+		// Factor a square matrix into combined L/U form with partial pivoting.
+		// Returns None if the matrix is singular.
+		assert!(self.m == self.n);
+		let n = self.m;
+		let mut data = self.data.clone();
+		let mut perm = vec::from_fn(n, |i:uint| -> uint { i });
+		let mut parity = 1.0;
+		for k in range(0, n) {
+			let mut pivot = k;
+			let mut pivot_val = data[k][k].abs();
+			for i in range(k+1, n) {
+				let val = data[i][k].abs();
+				if val > pivot_val {
+					pivot = i;
+					pivot_val = val;
+				}
+			}
+			if pivot_val == 0.0 {
+				return None;
+			}
+			if pivot != k {
+				let tmp = data[k].clone();
+				data[k] = data[pivot].clone();
+				data[pivot] = tmp;
+				let tmpi = perm[k];
+				perm[k] = perm[pivot];
+				perm[pivot] = tmpi;
+				parity = -parity;
+			}
+			for i in range(k+1, n) {
+				let l = data[i][k] / data[k][k];
+				data[i][k] = l;
+				for j in range(k+1, n) {
+					data[i][j] = data[i][j] - l*data[k][j];
+				}
+			}
+		}
+		Some(LUDecomposition{data: Matrix{m:n, n:n, data:data}, perm: perm, parity: parity})
+	}
+	pub fn inverse_via_adjugate(&self) -> Matrix<f64> {
+		// Invert using the classical adjugate/determinant formula.
+		let d = self.det();
+		assert!(d != 0.0);
+		self.adjugate().map(|x| { x / d })
+	}
+}
+
+impl LUDecomposition {
+	pub fn determinant(&self) -> f64 {
+		// Product of the diagonal of U, adjusted by the permutation parity.
+		let n = self.data.m;
+		let mut det = self.parity;
+		for i in range(0, n) {
+			det = det * self.data.at(i, i);
+		}
+		det
+	}
+	pub fn solve(&self, b : &Matrix<f64>) -> Matrix<f64> {
+		// Solve Ax = b via forward then back substitution, applying perm to b.
+		let n = self.data.m;
+		let mut y = vec::from_elem(n, 0.0);
+		for i in range(0, n) {
+			let mut sum = b.at(self.perm[i], 0);
+			for j in range(0, i) {
+				sum = sum - self.data.at(i, j) * y[j];
+			}
+			y[i] = sum;
+		}
+		let mut x = vec::from_elem(n, 0.0);
+		for ri in range(0, n) {
+			let i = n - 1 - ri;
+			let mut sum = y[i];
+			for j in range(i+1, n) {
+				sum = sum - self.data.at(i, j) * x[j];
+			}
+			x[i] = sum / self.data.at(i, i);
+		}
+		Matrix::from_fn(n, 1, |i,_| { x[i] })
+	}
+	pub fn inverse(&self) -> Matrix<f64> {
+		// Invert by solving against every column of the identity matrix.
+		let n = self.data.m;
+		let ident = identity(n);
+		let cols = vec::from_fn(n, |j:uint| -> Matrix<f64> { self.solve(&ident.col(j)) });
+		Matrix::from_fn(n, n, |i,j| { cols[j].at(i, 0) })
+	}
+}
+
 // convenience constructors
 pub fn zeros(m : uint, n : uint) -> Matrix<f64> {
 	// Create an MxN zero matrix of type f64.